@@ -0,0 +1,5 @@
+ALTER TABLE properties
+    ADD COLUMN IF NOT EXISTS agent TEXT,
+    ADD COLUMN IF NOT EXISTS agency TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_properties_agency ON properties(agency);
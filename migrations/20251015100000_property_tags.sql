@@ -0,0 +1,8 @@
+CREATE TABLE IF NOT EXISTS property_tags (
+    property_id TEXT NOT NULL REFERENCES properties(id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (property_id, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_property_tags_tag ON property_tags(tag);
@@ -0,0 +1,4 @@
+ALTER TABLE properties ADD COLUMN IF NOT EXISTS reference TEXT;
+
+-- Buyers quote references in any case, so lookups match case-insensitively
+CREATE INDEX IF NOT EXISTS idx_properties_reference ON properties(LOWER(reference));
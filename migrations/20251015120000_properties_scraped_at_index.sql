@@ -0,0 +1,2 @@
+-- Backs the stable (scraped_at, id) ordering used to page list endpoints
+CREATE INDEX IF NOT EXISTS idx_properties_scraped_at ON properties(scraped_at, id);
@@ -0,0 +1,4 @@
+-- Match the normalisation now applied on write: trimmed, no leading "Ref" label, NULL when blank
+UPDATE properties
+SET reference = NULLIF(REGEXP_REPLACE(TRIM(reference), '^ref[[:space:].:#]+', '', 'i'), '')
+WHERE reference IS NOT NULL;
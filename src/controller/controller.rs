@@ -1,20 +1,116 @@
-use actix_web::{web, get, post, put,  error};
-use actix_web::web::{Json, Path};
-use crate::models::property::{Property, PropertyNew};
+use actix_web::{web, get, post, put, patch, delete, error};
+use actix_web::web::{Json, Path, Query};
+use serde::{Deserialize, Serialize};
+use crate::models::page::{ListLimits, Page, Pagination};
+use crate::models::property::{normalize_reference, BulkUpdateFilter, Property, PropertyFilter, PropertyNew, PropertyPatch};
+use crate::models::report::{PriceDistribution, PropertyComparison, QualityReport};
 use crate::repository::property_repo::PropertyRepo;
+use crate::service::compare::compare_properties;
+use crate::service::seed::synthetic_properties;
 
 #[derive(Clone)]
 pub struct AppState {
     pub repository: PropertyRepo,
+    pub list_limits: ListLimits,
+    pub seeding_enabled: bool, // exposes POST /properties/seed, for local development only
 }
 
 #[get("")]
-pub async fn get_all_properties(state: web::Data<AppState>) -> actix_web::Result<Json<Vec<Property>>> {
-    log::info!("Requesting all properties");
+pub async fn get_all_properties(pagination: Query<Pagination>, state: web::Data<AppState>) -> actix_web::Result<Json<Page<Property>>> {
+    let (limit, offset) = pagination.resolve(state.list_limits);
+
+    log::info!("Requesting properties (limit: {limit}, offset: {offset})");
     
-    match state.repository.find_all_properties().await {
-        Ok(properties) => Ok(Json(properties)),
-        _ => Ok(Json(vec![]))
+    match state.repository.find_all_properties(limit, offset).await {
+        Ok(items) => Ok(Json(Page { items, limit, offset })),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+#[get("/ungeocoded")]
+pub async fn get_ungeocoded_properties(pagination: Query<Pagination>, state: web::Data<AppState>) -> actix_web::Result<Json<Page<Property>>> {
+    let (limit, offset) = pagination.resolve(state.list_limits);
+
+    log::info!("Requesting properties missing coordinates (limit: {limit}, offset: {offset})");
+
+    match state.repository.find_ungeocoded_properties(limit, offset).await {
+        Ok(items) => Ok(Json(Page { items, limit, offset })),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+#[get("/quality-report")]
+pub async fn get_quality_report(filter: Query<PropertyFilter>, state: web::Data<AppState>) -> actix_web::Result<Json<QualityReport>> {
+    log::info!("Requesting data-quality report");
+
+    match state.repository.quality_report(&filter).await {
+        Ok(report) => Ok(Json(report)),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+const DEFAULT_PRICE_BUCKETS: i32 = 10;
+const MAX_PRICE_BUCKETS: i32 = 100;
+
+#[derive(Deserialize)]
+pub struct PriceDistributionQuery {
+    pub buckets: Option<i32>,
+}
+
+#[get("/price-distribution")]
+pub async fn get_price_distribution(query: Query<PriceDistributionQuery>, filter: Query<PropertyFilter>, state: web::Data<AppState>) -> actix_web::Result<Json<PriceDistribution>> {
+    let buckets = query.buckets.unwrap_or(DEFAULT_PRICE_BUCKETS).clamp(1, MAX_PRICE_BUCKETS);
+
+    log::info!("Requesting price distribution over {buckets} buckets");
+
+    match state.repository.price_distribution(&filter, buckets).await {
+        Ok(distribution) => Ok(Json(distribution)),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+#[get("/tagged/{tag}")]
+pub async fn get_properties_by_tag(path: Path<String>, pagination: Query<Pagination>, state: web::Data<AppState>) -> actix_web::Result<Json<Page<Property>>> {
+    let tag = path.into_inner();
+    let (limit, offset) = pagination.resolve(state.list_limits);
+
+    log::info!("Requesting properties tagged {tag} (limit: {limit}, offset: {offset})");
+
+    match state.repository.find_properties_by_tag(tag.trim(), limit, offset).await {
+        Ok(items) => Ok(Json(Page { items, limit, offset })),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+#[get("/reference/{reference}")]
+pub async fn get_properties_by_reference(path: Path<String>, pagination: Query<Pagination>, state: web::Data<AppState>) -> actix_web::Result<Json<Page<Property>>> {
+    let reference = normalize_reference(&path.into_inner())
+        .ok_or_else(|| error::ErrorBadRequest("Reference must not be empty"))?;
+    let (limit, offset) = pagination.resolve(state.list_limits);
+
+    log::info!("Requesting properties with reference {reference} (limit: {limit}, offset: {offset})");
+
+    match state.repository.find_properties_by_reference(&reference, limit, offset).await {
+        Ok(items) => Ok(Json(Page { items, limit, offset })),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
     }
 }
 
@@ -48,6 +144,76 @@ pub async fn post_property(property: Json<PropertyNew>, state: web::Data<AppStat
     Ok(Json(property))
 }
 
+const DEFAULT_SEED_COUNT: usize = 100;
+const MAX_SEED_COUNT: usize = 10_000;
+
+#[derive(Deserialize)]
+pub struct SeedQuery {
+    pub count: Option<usize>,
+}
+
+#[derive(Serialize)]
+pub struct SeedResult {
+    pub requested: usize,
+    pub inserted: u64,
+}
+
+#[post("/seed")]
+pub async fn seed_properties(query: Query<SeedQuery>, state: web::Data<AppState>) -> actix_web::Result<Json<SeedResult>> {
+    if !state.seeding_enabled {
+        return Err(error::ErrorNotFound("Seeding is disabled"));
+    }
+
+    let requested = query.count.unwrap_or(DEFAULT_SEED_COUNT).min(MAX_SEED_COUNT);
+
+    log::info!("Seeding {requested} synthetic properties");
+
+    match state.repository.create_properties(&synthetic_properties(requested)).await {
+        Ok(inserted) => Ok(Json(SeedResult { requested, inserted })),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+const MAX_COMPARED_PROPERTIES: usize = 10;
+
+#[derive(Deserialize)]
+pub struct CompareRequest {
+    pub ids: Vec<String>,
+}
+
+#[post("/compare")]
+pub async fn post_property_comparison(request: Json<CompareRequest>, state: web::Data<AppState>) -> actix_web::Result<Json<PropertyComparison>> {
+    let mut ids = request.into_inner().ids;
+    let mut seen = std::collections::HashSet::new();
+    ids.retain(|id| seen.insert(id.clone()));
+
+    if ids.len() < 2 || ids.len() > MAX_COMPARED_PROPERTIES {
+        return Err(error::ErrorBadRequest(format!("Provide between 2 and {MAX_COMPARED_PROPERTIES} distinct property ids")));
+    }
+
+    log::info!("Comparing properties: {ids:?}");
+
+    let mut properties = match state.repository.find_properties_by_ids(&ids).await {
+        Ok(properties) => properties,
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            return Err(error::ErrorInternalServerError("Database error"));
+        }
+    };
+
+    if let Some(missing) = ids.iter().find(|id| !properties.iter().any(|property| &property.id == *id)) {
+        return Err(error::ErrorNotFound(format!("Property with id {} not found", missing)));
+    }
+    properties.sort_by_key(|property| ids.iter().position(|id| *id == property.id));
+
+    compare_properties(&properties)
+        .map(Json)
+        .map_err(|e| error::ErrorInternalServerError(e.to_string()))
+}
+
 #[put("/{id}")]
 pub async fn put_property(path: Path<String>, property: Json<PropertyNew>, state: web::Data<AppState>) -> actix_web::Result<Json<Property>> {
     let id = path.into_inner();
@@ -67,4 +233,102 @@ pub async fn put_property(path: Path<String>, property: Json<PropertyNew>, state
         }
     }
 
-}
\ No newline at end of file
+}
+
+#[derive(Serialize)]
+pub struct BulkUpdateResult {
+    pub updated: u64,
+}
+
+#[patch("")]
+pub async fn patch_properties(filter: Query<BulkUpdateFilter>, patch: Json<PropertyPatch>, state: web::Data<AppState>) -> actix_web::Result<Json<BulkUpdateResult>> {
+    // Refuse to blanket-update the whole table
+    filter.validate().map_err(error::ErrorBadRequest)?;
+    if patch.assignments().is_empty() {
+        return Err(error::ErrorBadRequest("No fields to update"));
+    }
+
+    log::info!("Bulk updating properties (city: {:?}, province: {:?}, type: {:?})", filter.city, filter.province, filter.property_type);
+
+    match state.repository.update_properties_by_filter(&filter, &patch).await {
+        Ok(updated) => Ok(Json(BulkUpdateResult { updated })),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+async fn ensure_property_exists(id: &str, state: &AppState) -> actix_web::Result<()> {
+    match state.repository.find_property_by_id(id.to_string()).await {
+        Ok(_) => Ok(()),
+        Err(sqlx::Error::RowNotFound) => {
+            Err(error::ErrorNotFound(format!("Property with id {} not found", id)))
+        }
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+async fn property_tags(id: &str, state: &AppState) -> actix_web::Result<Json<Vec<String>>> {
+    match state.repository.find_property_tags(id).await {
+        Ok(tags) => Ok(Json(tags)),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
+
+#[get("/{id}/tags")]
+pub async fn get_property_tags(path: Path<String>, state: web::Data<AppState>) -> actix_web::Result<Json<Vec<String>>> {
+    let id = path.into_inner();
+
+    ensure_property_exists(&id, &state).await?;
+    property_tags(&id, &state).await
+}
+
+#[put("/{id}/tags/{tag}")]
+pub async fn put_property_tag(path: Path<(String, String)>, state: web::Data<AppState>) -> actix_web::Result<Json<Vec<String>>> {
+    let (id, tag) = path.into_inner();
+    let tag = tag.trim();
+
+    if tag.is_empty() {
+        return Err(error::ErrorBadRequest("Tag must not be empty"));
+    }
+
+    log::info!("Tagging Property with ID: {id} as {tag}");
+
+    match state.repository.add_property_tag(&id, tag).await {
+        Ok(()) => {}
+        Err(sqlx::Error::Database(e)) if e.is_foreign_key_violation() => {
+            return Err(error::ErrorNotFound(format!("Property with id {} not found", id)));
+        }
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            return Err(error::ErrorInternalServerError("Database error"));
+        }
+    }
+
+    property_tags(&id, &state).await
+}
+
+#[delete("/{id}/tags/{tag}")]
+pub async fn delete_property_tag(path: Path<(String, String)>, state: web::Data<AppState>) -> actix_web::Result<Json<Vec<String>>> {
+    let (id, tag) = path.into_inner();
+
+    log::info!("Removing tag {tag} from Property with ID: {id}");
+
+    ensure_property_exists(&id, &state).await?;
+
+    match state.repository.remove_property_tag(&id, tag.trim()).await {
+        Ok(true) => property_tags(&id, &state).await,
+        Ok(false) => Err(error::ErrorNotFound(format!("Property with id {} has no tag {}", id, tag))),
+        Err(e) => {
+            log::error!("Database error: {}", e);
+            Err(error::ErrorInternalServerError("Database error"))
+        }
+    }
+}
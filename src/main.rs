@@ -1,6 +1,7 @@
 mod repository;
 mod models;
 mod controller;
+mod service;
 
 use actix_web::{
     get, middleware::Logger, post,
@@ -8,31 +9,115 @@ use actix_web::{
     Result,
 };
 
+use std::time::Duration;
+
 use shuttle_actix_web::ShuttleActixWeb;
-use sqlx::{ PgPool};
-use crate::controller::controller::{get_all_properties, get_property_by_id, post_property, put_property, AppState};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use crate::controller::controller::{
+    delete_property_tag, get_all_properties, get_price_distribution, get_properties_by_reference,
+    get_properties_by_tag, get_property_by_id, get_property_tags, get_quality_report,
+    get_ungeocoded_properties, patch_properties, post_property, post_property_comparison,
+    put_property, put_property_tag, seed_properties, AppState,
+};
+use crate::models::page::ListLimits;
 use crate::repository::property_repo::PropertyRepo;
 
+/// Attempts made to reach the database before giving up, overridable via `DB_WAIT_MAX_ATTEMPTS`
+const DEFAULT_DB_WAIT_MAX_ATTEMPTS: u32 = 5;
+/// Per-attempt connect timeout in seconds, overridable via `DB_WAIT_TIMEOUT_SECS`
+const DEFAULT_DB_WAIT_TIMEOUT_SECS: u64 = 30;
+/// Backoff before the second attempt; doubles after every failure
+const DB_WAIT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+/// Longest single backoff between attempts
+const DB_WAIT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// Page size for list endpoints when the client omits `limit`, overridable via `DEFAULT_LIST_LIMIT`
+const DEFAULT_LIST_LIMIT: i64 = 100;
+/// Largest page any list endpoint will return, overridable via `MAX_LIST_LIMIT`
+const MAX_LIST_LIMIT: i64 = 1000;
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    std::env::var(key)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Connect To The Database, Retrying With Capped Backoff While It Comes Up
+async fn connect_with_retry(conn_str: &str) -> Result<PgPool, shuttle_runtime::Error> {
+    let max_attempts = env_or("DB_WAIT_MAX_ATTEMPTS", DEFAULT_DB_WAIT_MAX_ATTEMPTS).max(1);
+    let timeout = Duration::from_secs(env_or("DB_WAIT_TIMEOUT_SECS", DEFAULT_DB_WAIT_TIMEOUT_SECS).max(1));
+    let mut backoff = DB_WAIT_INITIAL_BACKOFF;
+    let mut attempt = 1;
+
+    loop {
+        let connect = PgPoolOptions::new().acquire_timeout(timeout).connect(conn_str);
+        let error = match tokio::time::timeout(timeout, connect).await {
+            Ok(Ok(pool)) => return Ok(pool),
+            Ok(Err(e)) => e.to_string(),
+            Err(_) => format!("timed out after {}s", timeout.as_secs()),
+        };
+
+        if attempt == max_attempts {
+            return Err(shuttle_runtime::Error::Database(format!(
+                "Database unreachable after {max_attempts} attempts: {error}"
+            )));
+        }
+
+        log::warn!("Database connect {attempt}/{max_attempts} failed: {error}; retrying in {backoff:?}");
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(DB_WAIT_MAX_BACKOFF);
+        attempt += 1;
+    }
+}
+
+/// Run Migrations Once
+async fn run_migrations(pool: &PgPool) -> Result<(), shuttle_runtime::Error> {
+    // No timeout here: a slow migration must be allowed to finish, and cancelling one
+    // would leave sqlx's advisory lock held on a pooled connection
+    sqlx::migrate!()
+        .run(pool)
+        .await
+        .map_err(|e| shuttle_runtime::Error::Database(format!("Failed to run migrations: {e}")))
+}
+
 #[shuttle_runtime::main]
 async fn main(
-    #[shuttle_shared_db::Postgres] pool: PgPool,
+    // Injected as a connection string rather than a pool: the injected pool connects eagerly,
+    // so a database that is still starting would fail the deploy before main could retry
+    #[shuttle_shared_db::Postgres] conn_str: String,
 ) -> ShuttleActixWeb<impl FnOnce(&mut ServiceConfig) + Send + Clone + 'static> {
-    sqlx::migrate!()
-        .run(&pool)
-        .await
-        .expect("Failed to run migrations");
+    let pool = connect_with_retry(&conn_str).await?;
+    run_migrations(&pool).await?;
 
 
-    let state = web::Data::new(AppState { repository: PropertyRepo::new(pool) });
+    let state = web::Data::new(AppState {
+        repository: PropertyRepo::new(pool),
+        list_limits: ListLimits {
+            default_limit: env_or("DEFAULT_LIST_LIMIT", DEFAULT_LIST_LIMIT),
+            max_limit: env_or("MAX_LIST_LIMIT", MAX_LIST_LIMIT),
+        },
+        seeding_enabled: env_or("ENABLE_SEEDING", false),
+    });
 
     let config = move |cfg: &mut ServiceConfig| {
         cfg.service(
             web::scope("/properties")
                 .wrap(Logger::default())
                 .service(get_all_properties)
+                .service(get_ungeocoded_properties)
+                .service(get_quality_report)
+                .service(get_price_distribution)
+                .service(get_properties_by_tag)
+                .service(get_properties_by_reference)
                 .service(get_property_by_id)
+                .service(get_property_tags)
+                .service(put_property_tag)
+                .service(delete_property_tag)
                 .service(post_property)
+                .service(seed_properties)
+                .service(post_property_comparison)
                 .service(put_property)
+                .service(patch_properties)
                 .app_data(state),
         );
     };
@@ -1 +1,3 @@
-pub(crate) mod property;
\ No newline at end of file
+pub(crate) mod page;
+pub(crate) mod property;
+pub(crate) mod report;
\ No newline at end of file
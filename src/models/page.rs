@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+/// Bounds applied to every list endpoint
+#[derive(Clone, Copy)]
+pub struct ListLimits {
+    pub default_limit: i64,
+    pub max_limit: i64,
+}
+
+#[derive(Deserialize)]
+pub struct Pagination {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+impl Pagination {
+    /// Resolve the requested page into a (limit, offset) within the configured bounds
+    pub fn resolve(&self, limits: ListLimits) -> (i64, i64) {
+        let max_limit = limits.max_limit.max(1);
+        let limit = self.limit.unwrap_or(limits.default_limit).clamp(1, max_limit);
+
+        (limit, self.offset.unwrap_or(0).max(0))
+    }
+}
+
+#[derive(Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub limit: i64, // the limit actually applied, which may be lower than requested
+    pub offset: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ListLimits, Pagination};
+
+    const LIMITS: ListLimits = ListLimits { default_limit: 100, max_limit: 1000 };
+
+    fn page(limit: Option<i64>, offset: Option<i64>) -> Pagination {
+        Pagination { limit, offset }
+    }
+
+    #[test]
+    fn resolve_applies_default_and_clamps_limit() {
+        assert_eq!(page(None, None).resolve(LIMITS), (100, 0));
+        assert_eq!(page(Some(50), Some(20)).resolve(LIMITS), (50, 20));
+        assert_eq!(page(Some(5_000), None).resolve(LIMITS), (1000, 0));
+        assert_eq!(page(Some(0), None).resolve(LIMITS), (1, 0));
+        assert_eq!(page(Some(-10), None).resolve(LIMITS), (1, 0));
+    }
+
+    #[test]
+    fn resolve_rejects_negative_offsets() {
+        assert_eq!(page(None, Some(-5)).resolve(LIMITS), (100, 0));
+    }
+
+    #[test]
+    fn resolve_caps_a_default_above_the_max() {
+        let limits = ListLimits { default_limit: 500, max_limit: 200 };
+        assert_eq!(page(None, None).resolve(limits), (200, 0));
+    }
+
+    #[test]
+    fn resolve_treats_a_zero_max_as_one() {
+        let limits = ListLimits { default_limit: 100, max_limit: 0 };
+        assert_eq!(page(None, None).resolve(limits), (1, 0));
+    }
+}
@@ -19,6 +19,85 @@ pub struct PropertyNew {
     pub source_url: String,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
+    pub agent: Option<String>,
+    pub agency: Option<String>,
+    pub reference: Option<String>, // the agent's human-facing listing reference, e.g. "W1234"
+}
+
+/// Normalise A Listing Reference: Trimmed, Without A Leading "Ref" Label, None When Blank
+pub fn normalize_reference(reference: &str) -> Option<String> {
+    let reference = reference.trim();
+    let unlabelled = reference
+        .get(..3)
+        .filter(|label| label.eq_ignore_ascii_case("ref"))
+        .map(|_| &reference[3..])
+        .filter(|rest| rest.starts_with(|c: char| c.is_whitespace() || matches!(c, '.' | ':' | '#')))
+        .map(|rest| rest.trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '.' | ':' | '#')))
+        .unwrap_or(reference);
+
+    (!unlabelled.is_empty()).then(|| unlabelled.to_string())
+}
+
+/// Optional equality filters shared by the reporting endpoints
+#[derive(Deserialize)]
+pub struct PropertyFilter {
+    pub city: Option<String>,
+    pub province: Option<String>,
+    pub property_type: Option<String>,
+}
+
+/// Row selection for a bulk update. Unlike `PropertyFilter`, unknown keys are rejected
+/// so a misspelled or unsupported filter can't silently widen a destructive update
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BulkUpdateFilter {
+    pub city: Option<String>,
+    pub province: Option<String>,
+    pub property_type: Option<String>,
+}
+
+impl BulkUpdateFilter {
+    /// Require at least one filter and no blank values
+    pub fn validate(&self) -> Result<(), &'static str> {
+        let values = [&self.city, &self.province, &self.property_type];
+
+        if values.iter().all(|value| value.is_none()) {
+            return Err("At least one of city, province or property_type is required");
+        }
+        if values.into_iter().flatten().any(|value| value.trim().is_empty()) {
+            return Err("Filter values must not be empty");
+        }
+        Ok(())
+    }
+}
+
+/// Fields a bulk update may overwrite; unset fields are left untouched
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PropertyPatch {
+    pub province: Option<String>,
+    pub city: Option<String>,
+    pub suburb: Option<String>,
+    pub property_type: Option<String>,
+    pub agent: Option<String>,
+    pub agency: Option<String>,
+}
+
+impl PropertyPatch {
+    /// Column/value pairs for every field that is set
+    pub fn assignments(&self) -> Vec<(&'static str, &String)> {
+        [
+            ("province", &self.province),
+            ("city", &self.city),
+            ("suburb", &self.suburb),
+            ("property_type", &self.property_type),
+            ("agent", &self.agent),
+            ("agency", &self.agency),
+        ]
+        .into_iter()
+        .filter_map(|(column, value)| value.as_ref().map(|value| (column, value)))
+        .collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, FromRow)]
@@ -39,6 +118,9 @@ pub struct Property {
     pub source_url: String,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
+    pub agent: Option<String>,
+    pub agency: Option<String>,
+    pub reference: Option<String>, // the agent's human-facing listing reference, e.g. "W1234"
 }
 
 impl Property {
@@ -58,6 +140,9 @@ impl Property {
         source_url: String,
         latitude: Option<f64>,
         longitude: Option<f64>,
+        agent: Option<String>,
+        agency: Option<String>,
+        reference: Option<String>,
     ) -> Self {
         Self {
             id: Uuid::new_v4().to_string(),
@@ -76,6 +161,9 @@ impl Property {
             source_url,
             latitude,
             longitude,
+            agent,
+            agency,
+            reference,
         }
     }
     
@@ -95,7 +183,10 @@ impl Property {
             property.floor_size,
             property.source_url.clone(),
             property.latitude,
-            property.longitude,       
+            property.longitude,
+            property.agent.clone(),
+            property.agency.clone(),
+            property.reference.as_deref().and_then(normalize_reference),
         )
     }
 
@@ -117,6 +208,61 @@ impl Property {
             source_url: property.source_url.clone(),
             latitude: property.latitude,
             longitude: property.longitude,
+            agent: property.agent.clone(),
+            agency: property.agency.clone(),
+            reference: property.reference.as_deref().and_then(normalize_reference),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_reference, BulkUpdateFilter, PropertyPatch};
+
+    fn filter(city: Option<&str>, province: Option<&str>) -> BulkUpdateFilter {
+        BulkUpdateFilter {
+            city: city.map(str::to_string),
+            province: province.map(str::to_string),
+            property_type: None,
         }
     }
+
+    #[test]
+    fn bulk_update_filter_requires_a_non_blank_value() {
+        assert!(filter(None, None).validate().is_err());
+        assert!(filter(Some(""), None).validate().is_err());
+        assert!(filter(Some("Cape Town"), Some("  ")).validate().is_err());
+        assert!(filter(Some("Cape Town"), None).validate().is_ok());
+    }
+
+    #[test]
+    fn bulk_update_rejects_unknown_keys() {
+        assert!(serde_json::from_str::<PropertyPatch>(r#"{"city":"A","price":1}"#).is_err());
+        assert!(serde_json::from_str::<BulkUpdateFilter>(r#"{"city":"A","suburb":"B"}"#).is_err());
+    }
+
+    #[test]
+    fn patch_assignments_list_only_set_fields() {
+        let empty: PropertyPatch = serde_json::from_str("{}").unwrap();
+        assert!(empty.assignments().is_empty());
+
+        let patch: PropertyPatch = serde_json::from_str(r#"{"province":"Western Cape","agency":"Acme"}"#).unwrap();
+        let assignments: Vec<(&str, &str)> = patch
+            .assignments()
+            .into_iter()
+            .map(|(column, value)| (column, value.as_str()))
+            .collect();
+        assert_eq!(assignments, vec![("province", "Western Cape"), ("agency", "Acme")]);
+    }
+
+    #[test]
+    fn references_are_trimmed_and_unlabelled() {
+        assert_eq!(normalize_reference("  W1234 ").as_deref(), Some("W1234"));
+        assert_eq!(normalize_reference("Ref W1234").as_deref(), Some("W1234"));
+        assert_eq!(normalize_reference("REF: W1234").as_deref(), Some("W1234"));
+        assert_eq!(normalize_reference("ref#W1234").as_deref(), Some("W1234"));
+        // A code that merely starts with the letters is left alone
+        assert_eq!(normalize_reference("REF1234").as_deref(), Some("REF1234"));
+        assert_eq!(normalize_reference("   "), None);
+    }
 }
@@ -0,0 +1,96 @@
+use std::collections::BTreeMap;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct NumericSummary {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+}
+
+#[derive(Serialize)]
+pub struct QualityReport {
+    pub total: i64,
+    pub null_percentages: BTreeMap<String, f64>, // 0.0 - 100.0 per nullable column
+    pub numeric: BTreeMap<String, NumericSummary>,
+    pub distinct_counts: BTreeMap<String, i64>,
+    pub out_of_range_coordinates: i64,
+    pub price_outliers: i64, // outside 1.5 * IQR of the filtered prices
+}
+
+#[derive(Serialize)]
+pub struct FieldComparison {
+    pub field: String,
+    pub values: Vec<serde_json::Value>, // one per compared property, in request order
+    pub differs: bool,
+}
+
+#[derive(Serialize)]
+pub struct PropertyComparison {
+    pub ids: Vec<String>,
+    pub fields: Vec<FieldComparison>,
+}
+
+#[derive(Serialize)]
+pub struct PriceBucket {
+    pub lower: i64, // inclusive
+    pub upper: i64, // exclusive
+    pub count: i64,
+}
+
+/// Integer bucket width covering `min..=max` in `buckets` buckets, rounded up.
+/// Computed in i128 because the span of two extreme i64 prices doesn't fit in an i64.
+pub fn price_bucket_width(min: i64, max: i64, buckets: i64) -> i128 {
+    let span = max as i128 - min as i128 + 1;
+    let buckets = buckets.max(1) as i128;
+
+    (span + buckets - 1) / buckets
+}
+
+/// Half-open `[lower, upper)` bounds of equal integer width covering `min..=max`.
+/// The width is rounded up, so fewer buckets than requested may be needed to cover the range.
+/// An upper bound past `i64::MAX` saturates to it.
+pub fn price_bucket_bounds(min: i64, max: i64, buckets: i64) -> Vec<(i64, i64)> {
+    let span = max as i128 - min as i128 + 1;
+    let width = price_bucket_width(min, max, buckets);
+    let count = (span + width - 1) / width;
+    let bound = |i: i128| (min as i128 + i * width).min(i64::MAX as i128) as i64;
+
+    (0..count).map(|i| (bound(i), bound(i + 1))).collect()
+}
+
+#[derive(Serialize)]
+pub struct PriceDistribution {
+    pub total: i64,
+    pub buckets: Vec<PriceBucket>, // equal-width, empty when no property has a price
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{price_bucket_bounds, price_bucket_width};
+
+    #[test]
+    fn bucket_bounds_use_integer_width() {
+        // A price of 3 falls in the first bucket, matching (price - min) / width + 1
+        assert_eq!(price_bucket_bounds(0, 9, 3), vec![(0, 4), (4, 8), (8, 12)]);
+        assert_eq!(price_bucket_bounds(100, 199, 4), vec![(100, 125), (125, 150), (150, 175), (175, 200)]);
+    }
+
+    #[test]
+    fn bucket_bounds_drop_buckets_past_max() {
+        assert_eq!(price_bucket_bounds(0, 9, 6), vec![(0, 2), (2, 4), (4, 6), (6, 8), (8, 10)]);
+    }
+
+    #[test]
+    fn bucket_bounds_collapse_when_all_prices_equal() {
+        assert_eq!(price_bucket_bounds(500_000, 500_000, 10), vec![(500_000, 500_001)]);
+    }
+
+    #[test]
+    fn bucket_bounds_handle_extreme_prices() {
+        assert_eq!(price_bucket_width(i64::MIN, i64::MAX, 2), 1i128 << 63);
+        assert_eq!(price_bucket_bounds(i64::MIN, i64::MAX, 2), vec![(i64::MIN, 0), (0, i64::MAX)]);
+        assert_eq!(price_bucket_bounds(-5, i64::MAX, 1), vec![(-5, i64::MAX)]);
+        assert_eq!(price_bucket_bounds(i64::MAX, i64::MAX, 10), vec![(i64::MAX, i64::MAX)]);
+    }
+}
@@ -1,6 +1,56 @@
+use std::collections::BTreeMap;
 use actix_web::web::Json;
-use sqlx::PgPool;
-use crate::models::property::{Property, PropertyNew};
+use sqlx::postgres::PgArguments;
+use sqlx::query::QueryAs;
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+use crate::models::property::{BulkUpdateFilter, Property, PropertyFilter, PropertyNew, PropertyPatch};
+use crate::models::report::{price_bucket_bounds, price_bucket_width, NumericSummary, PriceBucket, PriceDistribution, QualityReport};
+
+/// Matches every row when a filter field is unset; binds city, province, property_type
+const FILTER_CLAUSE: &str = "($1::TEXT IS NULL OR city = $1)
+    AND ($2::TEXT IS NULL OR province = $2)
+    AND ($3::TEXT IS NULL OR property_type = $3)";
+
+const NULLABLE_COLUMNS: [&str; 12] = [
+    "price", "suburb", "bedrooms", "bathrooms", "garage_spaces", "land_size",
+    "floor_size", "latitude", "longitude", "agent", "agency", "reference",
+];
+const NUMERIC_COLUMNS: [&str; 6] = [
+    "price", "bedrooms", "bathrooms", "garage_spaces", "land_size", "floor_size",
+];
+const CATEGORICAL_COLUMNS: [&str; 5] = ["province", "city", "suburb", "property_type", "agency"];
+
+/// Every column written on create, in the order `bind_insert` binds them
+const INSERT_PROPERTY: &str = "INSERT INTO properties(
+    id, title, price, address, province, city, suburb,
+    property_type, bedrooms, bathrooms, garage_spaces,
+    land_size, floor_size, source_url,
+    latitude, longitude, agent, agency, reference)
+VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19)";
+
+/// Bind A Property To An `INSERT_PROPERTY` Statement
+fn bind_insert<'q>(sql: &'q str, property: &'q Property) -> QueryAs<'q, Postgres, Property, PgArguments> {
+    sqlx::query_as(sql)
+        .bind(&property.id)
+        .bind(&property.title)
+        .bind(property.price)
+        .bind(&property.address)
+        .bind(&property.province)
+        .bind(&property.city)
+        .bind(&property.suburb)
+        .bind(&property.property_type)
+        .bind(property.bedrooms)
+        .bind(property.bathrooms)
+        .bind(property.garage_spaces)
+        .bind(property.land_size)
+        .bind(property.floor_size)
+        .bind(&property.source_url)
+        .bind(property.latitude)
+        .bind(property.longitude)
+        .bind(&property.agent)
+        .bind(&property.agency)
+        .bind(&property.reference)
+}
 
 #[derive(Clone)]
 pub struct PropertyRepo {
@@ -13,8 +63,10 @@ impl PropertyRepo {
     }
 
     /// Get All Properties From The Properties Database
-    pub async fn find_all_properties(&self) -> Result<Vec<Property>, sqlx::Error> {
-        sqlx::query_as("SELECT * FROM properties")
+    pub async fn find_all_properties(&self, limit: i64, offset: i64) -> Result<Vec<Property>, sqlx::Error> {
+        sqlx::query_as("SELECT * FROM properties ORDER BY scraped_at DESC, id DESC LIMIT $1 OFFSET $2")
+            .bind(limit)
+            .bind(offset)
             .fetch_all(&self.pool)
             .await
     }
@@ -27,38 +79,237 @@ impl PropertyRepo {
             .await
     }
 
+    /// Find Properties By IDs, In No Particular Order
+    pub async fn find_properties_by_ids(&self, ids: &[String]) -> Result<Vec<Property>, sqlx::Error> {
+        sqlx::query_as("SELECT * FROM properties WHERE id = ANY($1)")
+            .bind(ids)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Find Properties By Listing Reference, Ignoring Case
+    pub async fn find_properties_by_reference(&self, reference: &str, limit: i64, offset: i64) -> Result<Vec<Property>, sqlx::Error> {
+        sqlx::query_as("
+            SELECT * FROM properties
+            WHERE LOWER(reference) = LOWER($1)
+            ORDER BY scraped_at DESC, id DESC
+            LIMIT $2 OFFSET $3
+            ")
+            .bind(reference)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Find Properties With An Address But No Coordinates
+    pub async fn find_ungeocoded_properties(&self, limit: i64, offset: i64) -> Result<Vec<Property>, sqlx::Error> {
+        sqlx::query_as("
+            SELECT * FROM properties
+            WHERE (latitude IS NULL OR longitude IS NULL) AND address IS NOT NULL AND address <> ''
+            ORDER BY scraped_at, id
+            LIMIT $1 OFFSET $2
+            ")
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Find Properties Carrying A Tag
+    pub async fn find_properties_by_tag(&self, tag: &str, limit: i64, offset: i64) -> Result<Vec<Property>, sqlx::Error> {
+        sqlx::query_as("
+            SELECT p.* FROM properties p
+            JOIN property_tags t ON t.property_id = p.id
+            WHERE t.tag = $1
+            ORDER BY t.created_at, p.id
+            LIMIT $2 OFFSET $3
+            ")
+            .bind(tag)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Find The Tags On A Property
+    pub async fn find_property_tags(&self, id: &str) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar("SELECT tag FROM property_tags WHERE property_id = $1 ORDER BY tag")
+            .bind(id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// Tag A Property; Tagging Twice Is A No-Op
+    pub async fn add_property_tag(&self, id: &str, tag: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("INSERT INTO property_tags(property_id, tag) VALUES ($1, $2) ON CONFLICT DO NOTHING")
+            .bind(id)
+            .bind(tag)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Remove A Tag From A Property, Returning Whether It Was Present
+    pub async fn remove_property_tag(&self, id: &str, tag: &str) -> Result<bool, sqlx::Error> {
+        let result = sqlx::query("DELETE FROM property_tags WHERE property_id = $1 AND tag = $2")
+            .bind(id)
+            .bind(tag)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Compute A Data-Quality Report Over The Filtered Properties
+    pub async fn quality_report(&self, filter: &PropertyFilter) -> Result<QualityReport, sqlx::Error> {
+        let mut columns = vec!["COUNT(*) AS total".to_string()];
+        columns.extend(NULLABLE_COLUMNS.iter().map(|c| format!("COUNT({c}) AS {c}_present")));
+        columns.extend(NUMERIC_COLUMNS.iter().map(|c| {
+            format!("MIN({c})::FLOAT8 AS {c}_min, MAX({c})::FLOAT8 AS {c}_max, AVG({c})::FLOAT8 AS {c}_mean")
+        }));
+        columns.extend(CATEGORICAL_COLUMNS.iter().map(|c| format!("COUNT(DISTINCT {c}) AS {c}_distinct")));
+        columns.push("COUNT(*) FILTER (
+            WHERE latitude NOT BETWEEN -90 AND 90 OR longitude NOT BETWEEN -180 AND 180
+        ) AS out_of_range_coordinates".to_string());
+
+        let sql = format!("SELECT {} FROM properties WHERE {FILTER_CLAUSE}", columns.join(", "));
+        let row = sqlx::query(&sql)
+            .bind(&filter.city)
+            .bind(&filter.province)
+            .bind(&filter.property_type)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let total: i64 = row.try_get("total")?;
+
+        let mut null_percentages = BTreeMap::new();
+        for column in NULLABLE_COLUMNS {
+            let present: i64 = row.try_get(format!("{column}_present").as_str())?;
+            let percentage = if total == 0 { 0.0 } else { (total - present) as f64 * 100.0 / total as f64 };
+            null_percentages.insert(column.to_string(), percentage);
+        }
+
+        let mut numeric = BTreeMap::new();
+        for column in NUMERIC_COLUMNS {
+            numeric.insert(column.to_string(), NumericSummary {
+                min: row.try_get(format!("{column}_min").as_str())?,
+                max: row.try_get(format!("{column}_max").as_str())?,
+                mean: row.try_get(format!("{column}_mean").as_str())?,
+            });
+        }
+
+        let mut distinct_counts = BTreeMap::new();
+        for column in CATEGORICAL_COLUMNS {
+            distinct_counts.insert(column.to_string(), row.try_get(format!("{column}_distinct").as_str())?);
+        }
+
+        let price_outliers: i64 = sqlx::query_scalar(&format!("
+            WITH filtered AS (
+                SELECT price FROM properties WHERE price IS NOT NULL AND {FILTER_CLAUSE}
+            ), bounds AS (
+                SELECT percentile_cont(0.25) WITHIN GROUP (ORDER BY price::FLOAT8) AS q1,
+                       percentile_cont(0.75) WITHIN GROUP (ORDER BY price::FLOAT8) AS q3
+                FROM filtered
+            )
+            SELECT COUNT(*) FROM filtered, bounds
+            WHERE price < q1 - 1.5 * (q3 - q1) OR price > q3 + 1.5 * (q3 - q1)
+            "))
+            .bind(&filter.city)
+            .bind(&filter.province)
+            .bind(&filter.property_type)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(QualityReport {
+            total,
+            null_percentages,
+            numeric,
+            distinct_counts,
+            out_of_range_coordinates: row.try_get("out_of_range_coordinates")?,
+            price_outliers,
+        })
+    }
+
+    /// Count Filtered Properties Into Equal-Width Price Buckets
+    pub async fn price_distribution(&self, filter: &PropertyFilter, buckets: i32) -> Result<PriceDistribution, sqlx::Error> {
+        let (min, max): (Option<i64>, Option<i64>) = sqlx::query_as(&format!(
+            "SELECT MIN(price), MAX(price) FROM properties WHERE price IS NOT NULL AND {FILTER_CLAUSE}"
+        ))
+            .bind(&filter.city)
+            .bind(&filter.province)
+            .bind(&filter.property_type)
+            .fetch_one(&self.pool)
+            .await?;
+
+        let (Some(min), Some(max)) = (min, max) else {
+            return Ok(PriceDistribution { total: 0, buckets: vec![] });
+        };
+
+        let bounds = price_bucket_bounds(min, max, buckets as i64);
+        let width = price_bucket_width(min, max, buckets as i64);
+
+        // Bucketed with the same integer width as the reported bounds so counts and ranges agree.
+        // NUMERIC keeps price - min from overflowing BIGINT, and the width (which can exceed
+        // i64::MAX for extreme ranges) is passed as text since sqlx can't bind an i128
+        let counts: Vec<(i64, i64)> = sqlx::query_as(&format!("
+            SELECT FLOOR((price::NUMERIC - $4::BIGINT) / $5::TEXT::NUMERIC)::BIGINT + 1 AS bucket, COUNT(*)
+            FROM properties
+            WHERE price BETWEEN $4::BIGINT AND $6::BIGINT AND {FILTER_CLAUSE}
+            GROUP BY bucket
+            "))
+            .bind(&filter.city)
+            .bind(&filter.province)
+            .bind(&filter.property_type)
+            .bind(min)
+            .bind(width.to_string())
+            .bind(max)
+            .fetch_all(&self.pool)
+            .await?;
+
+        let buckets = bounds
+            .into_iter()
+            .zip(1..)
+            .map(|((lower, upper), bucket)| PriceBucket {
+                lower,
+                upper,
+                count: counts.iter().find(|row| row.0 == bucket).map_or(0, |row| row.1),
+            })
+            .collect();
+
+        Ok(PriceDistribution {
+            total: counts.iter().map(|row| row.1).sum(),
+            buckets,
+        })
+    }
+
     /// Create Property
     pub async fn create_property(&self, property: Json<PropertyNew>) -> Result<Property, sqlx::Error> {
 
         let property = Property::from(&property);
 
-        sqlx::query_as("INSERT INTO properties(
-            id, title, price, address, province, city, suburb,
-            property_type, bedrooms, bathrooms, garage_spaces,
-            land_size, floor_size, source_url,
-            latitude, longitude)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
-        RETURNING * ")
-            .bind(&property.id)
-            .bind(&property.title)
-            .bind(&property.price)
-            .bind(&property.address)
-            .bind(&property.province)
-            .bind(&property.city)
-            .bind(&property.suburb)
-            .bind(&property.property_type)
-            .bind(&property.bedrooms)
-            .bind(&property.bathrooms)
-            .bind(&property.garage_spaces)
-            .bind(&property.land_size)
-            .bind(&property.floor_size)
-            .bind(&property.source_url)
-            .bind(&property.latitude)
-            .bind(&property.longitude)
+        bind_insert(&format!("{INSERT_PROPERTY} RETURNING *"), &property)
             .fetch_one(&self.pool)
             .await
     }
 
+    /// Create Many Properties In One Transaction, Skipping Known Source URLs
+    pub async fn create_properties(&self, properties: &[PropertyNew]) -> Result<u64, sqlx::Error> {
+        let sql = format!("{INSERT_PROPERTY} ON CONFLICT (source_url) DO NOTHING RETURNING *");
+        let mut tx = self.pool.begin().await?;
+        let mut inserted = 0;
+
+        for property in properties {
+            let property = Property::from(property);
+
+            if bind_insert(&sql, &property).fetch_optional(&mut *tx).await?.is_some() {
+                inserted += 1;
+            }
+        }
+
+        tx.commit().await?;
+        Ok(inserted)
+    }
+
     /// Update Property By ID
     pub async fn update_property_by_id(&self, id: String, property: Json<PropertyNew>) -> Result<Property, sqlx::Error> {
         let property = Property::new_with_id(id, &property);
@@ -68,8 +319,8 @@ impl PropertyRepo {
             SET title = $1, price = $2, address = $3, province = $4, city = $5, suburb = $6,
             property_type = $7, bedrooms = $8, bathrooms = $9, garage_spaces = $10,
             land_size = $11, floor_size = $12, source_url = $13,
-            latitude = $14, longitude = $15
-            WHERE id = $16
+            latitude = $14, longitude = $15, agent = $16, agency = $17, reference = $18
+            WHERE id = $19
             ")
             .bind(&property.title)
             .bind(&property.price)
@@ -86,11 +337,35 @@ impl PropertyRepo {
             .bind(&property.source_url)
             .bind(&property.latitude)
             .bind(&property.longitude)
+            .bind(&property.agent)
+            .bind(&property.agency)
+            .bind(&property.reference)
             .bind(&property.id)
             .fetch_one(&self.pool)
             .await
     }
 
+    /// Apply A Patch To Every Property Matching The Filter, Returning The Rows Changed
+    pub async fn update_properties_by_filter(&self, filter: &BulkUpdateFilter, patch: &PropertyPatch) -> Result<u64, sqlx::Error> {
+        let mut query = QueryBuilder::<Postgres>::new("UPDATE properties SET updated_at = NOW()");
+        for (column, value) in patch.assignments() {
+            query.push(", ").push(column).push(" = ").push_bind(value);
+        }
+
+        query.push(" WHERE TRUE");
+        if let Some(city) = &filter.city {
+            query.push(" AND city = ").push_bind(city);
+        }
+        if let Some(province) = &filter.province {
+            query.push(" AND province = ").push_bind(province);
+        }
+        if let Some(property_type) = &filter.property_type {
+            query.push(" AND property_type = ").push_bind(property_type);
+        }
+
+        Ok(query.build().execute(&self.pool).await?.rows_affected())
+    }
+
     /// Delete Property By ID
     pub async fn delete_property_by_id(&self, id: &str) -> Result<Property, sqlx::Error> {
         todo!()
@@ -0,0 +1,96 @@
+use serde_json::Value;
+use crate::models::property::Property;
+use crate::models::report::{FieldComparison, PropertyComparison};
+
+/// Lay Properties Out Field By Field, Flagging Fields Whose Values Differ
+pub fn compare_properties(properties: &[Property]) -> Result<PropertyComparison, serde_json::Error> {
+    let rows = properties
+        .iter()
+        .map(|property| match serde_json::to_value(property)? {
+            Value::Object(fields) => Ok(fields),
+            _ => unreachable!("Property serializes to a JSON object"),
+        })
+        .collect::<Result<Vec<_>, serde_json::Error>>()?;
+
+    let fields = rows
+        .first()
+        .map(|first| first.keys().filter(|field| *field != "id").cloned().collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|field| {
+            let values: Vec<Value> = rows.iter().map(|row| row[field.as_str()].clone()).collect();
+            let differs = values.windows(2).any(|pair| pair[0] != pair[1]);
+            FieldComparison { field, values, differs }
+        })
+        .collect();
+
+    Ok(PropertyComparison {
+        ids: properties.iter().map(|property| property.id.clone()).collect(),
+        fields,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compare_properties;
+    use crate::models::property::Property;
+
+    fn property(id: &str, price: Option<i64>, city: &str) -> Property {
+        Property {
+            id: id.to_string(),
+            title: "3 Bedroom house".to_string(),
+            price,
+            address: "1 Main Street".to_string(),
+            province: "Western Cape".to_string(),
+            city: city.to_string(),
+            suburb: None,
+            property_type: "house".to_string(),
+            bedrooms: Some(3),
+            bathrooms: Some(2),
+            garage_spaces: None,
+            land_size: None,
+            floor_size: Some(180.0),
+            source_url: format!("https://example.com/{id}"),
+            latitude: None,
+            longitude: None,
+            agent: None,
+            agency: None,
+            reference: None,
+        }
+    }
+
+    #[test]
+    fn flags_only_fields_that_differ() {
+        let comparison = compare_properties(&[
+            property("a", Some(1_500_000), "Cape Town"),
+            property("b", Some(1_750_000), "Cape Town"),
+        ])
+        .unwrap();
+
+        assert_eq!(comparison.ids, vec!["a", "b"]);
+        assert!(comparison.fields.iter().all(|field| field.field != "id"));
+
+        let differs = |name: &str| comparison.fields.iter().find(|field| field.field == name).unwrap().differs;
+        assert!(differs("price"));
+        assert!(differs("source_url"));
+        assert!(!differs("city"));
+        assert!(!differs("bedrooms"));
+        // Two missing values are equal, not different
+        assert!(!differs("suburb"));
+    }
+
+    #[test]
+    fn keeps_values_in_request_order() {
+        let comparison = compare_properties(&[
+            property("b", None, "Durban"),
+            property("a", Some(900_000), "Cape Town"),
+        ])
+        .unwrap();
+
+        let city = comparison.fields.iter().find(|field| field.field == "city").unwrap();
+        assert_eq!(city.values, vec!["Durban", "Cape Town"]);
+
+        let price = comparison.fields.iter().find(|field| field.field == "price").unwrap();
+        assert_eq!(price.values, vec![serde_json::Value::Null, serde_json::json!(900_000)]);
+    }
+}
@@ -1 +1,3 @@
-mod scraper;
\ No newline at end of file
+pub(crate) mod compare;
+mod scraper;
+pub(crate) mod seed;
\ No newline at end of file
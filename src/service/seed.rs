@@ -0,0 +1,94 @@
+use uuid::Uuid;
+use crate::models::property::PropertyNew;
+
+// (city, province, latitude, longitude, suburbs)
+const CITIES: [(&str, &str, f64, f64, [&str; 3]); 8] = [
+    ("Johannesburg", "Gauteng", -26.2041, 28.0473, ["Sandton", "Rosebank", "Melville"]),
+    ("Pretoria", "Gauteng", -25.7479, 28.2293, ["Hatfield", "Brooklyn", "Centurion"]),
+    ("Cape Town", "Western Cape", -33.9249, 18.4241, ["Sea Point", "Claremont", "Observatory"]),
+    ("Stellenbosch", "Western Cape", -33.9321, 18.8602, ["Die Boord", "Paradyskloof", "Welgevonden"]),
+    ("Durban", "KwaZulu-Natal", -29.8587, 31.0218, ["Umhlanga", "Morningside", "Berea"]),
+    ("Gqeberha", "Eastern Cape", -33.9608, 25.6022, ["Summerstrand", "Walmer", "Newton Park"]),
+    ("Bloemfontein", "Free State", -29.0852, 26.1596, ["Westdene", "Universitas", "Langenhoven Park"]),
+    ("Polokwane", "Limpopo", -23.9045, 29.4689, ["Bendor", "Flora Park", "Fauna Park"]),
+];
+
+// (property_type, min price, max price, min floor size, max floor size)
+const TYPES: [(&str, i64, i64, f64, f64); 4] = [
+    ("house", 900_000, 12_000_000, 90.0, 600.0),
+    ("apartment", 500_000, 6_000_000, 35.0, 220.0),
+    ("townhouse", 700_000, 4_500_000, 70.0, 260.0),
+    ("commercial", 1_500_000, 40_000_000, 150.0, 5_000.0),
+];
+
+/// Small xorshift generator; demo data doesn't justify a `rand` dependency
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn range(&mut self, min: i64, max: i64) -> i64 {
+        min + (self.next() % (max - min + 1) as u64) as i64
+    }
+
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.next() as usize % items.len()]
+    }
+}
+
+/// Generate Realistic Synthetic Properties For Local Development
+pub fn synthetic_properties(count: usize) -> Vec<PropertyNew> {
+    let mut rng = Rng(Uuid::new_v4().as_u64_pair().0 | 1);
+
+    (0..count)
+        .map(|_| {
+            let (city, province, latitude, longitude, suburbs) = rng.pick(&CITIES);
+            let (property_type, min_price, max_price, min_floor, max_floor) = rng.pick(&TYPES);
+            let suburb = rng.pick(suburbs);
+            let commercial = *property_type == "commercial";
+
+            let bedrooms = (!commercial).then(|| rng.range(1, 5) as i16);
+            let floor_size = (min_floor + rng.unit() * (max_floor - min_floor)).round();
+            let land_size = (*property_type == "house" || commercial)
+                .then(|| (floor_size * (1.5 + rng.unit() * 2.0)).round());
+            // Round to the nearest R5 000 like real listings
+            let price = rng.range(*min_price, *max_price) / 5_000 * 5_000;
+
+            let title = match bedrooms {
+                Some(beds) => format!("{beds} Bedroom {property_type} in {suburb}"),
+                None => format!("Commercial property in {suburb}"),
+            };
+
+            PropertyNew {
+                title,
+                price: Some(price),
+                address: format!("{} {} Street, {suburb}", rng.range(1, 250), rng.pick(&["Main", "Church", "Oak", "Park", "Beach"])),
+                province: province.to_string(),
+                city: city.to_string(),
+                suburb: Some(suburb.to_string()),
+                property_type: property_type.to_string(),
+                bedrooms,
+                bathrooms: bedrooms.map(|beds| rng.range(1, beds.max(1) as i64) as i16),
+                garage_spaces: Some(rng.range(0, 3) as i16),
+                land_size,
+                floor_size: Some(floor_size),
+                source_url: format!("https://example.com/seed/{}", Uuid::new_v4()),
+                // Scatter within roughly 10km of the city centre
+                latitude: Some(latitude + (rng.unit() - 0.5) * 0.18),
+                longitude: Some(longitude + (rng.unit() - 0.5) * 0.18),
+                agent: None,
+                agency: None,
+                reference: None,
+            }
+        })
+        .collect()
+}